@@ -0,0 +1,92 @@
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+/// Which toolchain performs PNG -> WebP/AVIF conversion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Encoder {
+  /// Shell out to `cwebp`/`avifenc`.
+  Cli,
+  /// Encode in-process via the `image`/`webp`/`ravif` crates; no external binaries required.
+  Native,
+}
+
+#[derive(Parser)]
+#[command(name = "figma-image-tool", about = "Export and convert images from Figma files")]
+pub struct Cli {
+  #[command(subcommand)]
+  pub command: Commands,
+}
+
+#[derive(Subcommand)]
+pub enum Commands {
+  /// Download every exportable frame from the configured Figma file, or from
+  /// each entry listed in the `--file` batch file.
+  Download {
+    /// Directory to write downloaded images into.
+    #[arg(short, long, default_value = "./downloads")]
+    download_dir: PathBuf,
+
+    /// Maximum number of downloads to run concurrently.
+    #[arg(long, default_value_t = 8, value_parser = clap::value_parser!(usize).range(1..))]
+    concurrency: usize,
+
+    /// Maximum number of retry attempts for a failed download.
+    #[arg(long, default_value_t = 3)]
+    max_retries: u32,
+
+    /// Newline-delimited file of Figma file keys to export in one run.
+    /// Each line is a file key, optionally suffixed with `:nodeId` to
+    /// restrict that entry to a single node. Blank lines are skipped. Each
+    /// entry is downloaded into its own subdirectory of `download_dir`
+    /// named after the file key.
+    #[arg(long)]
+    file: Option<PathBuf>,
+
+    /// Format to request from the Figma export API: "png", "svg", "jpg", or "pdf".
+    #[arg(long, default_value = "png")]
+    export_format: String,
+
+    /// Export scale factor (e.g. 2.0 for a retina raster export).
+    #[arg(long, default_value_t = 1.0)]
+    scale: f32,
+
+    /// Always render progress bars, even when stdout isn't a terminal.
+    #[arg(long, conflicts_with = "quiet")]
+    progress: bool,
+
+    /// Never render progress bars; only plain log lines.
+    #[arg(long)]
+    quiet: bool,
+  },
+  /// Convert downloaded PNGs to a web-friendly format.
+  Convert {
+    /// Directory containing the PNGs to convert.
+    #[arg(short, long, default_value = "./downloads")]
+    input_dir: PathBuf,
+
+    /// Directory to write converted files into.
+    #[arg(short, long, default_value = "./converted")]
+    output_dir: PathBuf,
+
+    /// Target format: "webp" or "avif".
+    #[arg(short, long, default_value = "webp")]
+    format: String,
+
+    /// Toolchain to encode with: "cli" (cwebp/avifenc) or "native" (pure-Rust, no external binaries).
+    #[arg(long, value_enum, default_value_t = Encoder::Cli)]
+    encoder: Encoder,
+
+    /// Lossy compression quality, 0-100.
+    #[arg(long, default_value_t = 80)]
+    quality: u8,
+
+    /// Always render progress bars, even when stdout isn't a terminal.
+    #[arg(long, conflicts_with = "quiet")]
+    progress: bool,
+
+    /// Never render progress bars; only plain log lines.
+    #[arg(long)]
+    quiet: bool,
+  },
+}
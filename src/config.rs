@@ -0,0 +1,30 @@
+use std::env;
+
+use crate::errors::FigmaToolError;
+
+/// Configuration pulled from the environment (`.env` or process env).
+///
+/// Figma requires a personal access token on every request. `FIGMA_FILE_KEY`
+/// is optional: it's the default file to export when the caller doesn't
+/// supply one explicitly (e.g. via `--file` batch mode).
+pub struct Config {
+  pub figma_token: String,
+  pub default_file_key: Option<String>,
+}
+
+impl Config {
+  /// Loads configuration from the environment, applying `.env` first if one
+  /// is present in the working directory.
+  pub fn from_env() -> Result<Self, FigmaToolError> {
+    dotenvy::dotenv().ok();
+
+    let figma_token = env::var("FIGMA_TOKEN")
+      .map_err(|_| FigmaToolError::Config("FIGMA_TOKEN environment variable is not set".to_string()))?;
+    let default_file_key = env::var("FIGMA_FILE_KEY").ok();
+
+    Ok(Self {
+      figma_token,
+      default_file_key,
+    })
+  }
+}
@@ -0,0 +1,106 @@
+use imgref::Img;
+use ravif::Encoder as AvifEncoder;
+use rgb::FromSlice;
+use tokio::process::Command;
+use webp::Encoder as WebpEncoder;
+
+use crate::errors::FigmaToolError;
+
+/// Shells out to platform image tooling, or encodes in-process via the
+/// `image`/`webp`/`ravif` crates, to convert exported PNGs to smaller
+/// web-friendly formats.
+pub struct ImageConverter;
+
+impl ImageConverter {
+  /// Returns whether the `cwebp` binary is reachable on `PATH`.
+  pub fn check_cwebp_installed() -> bool {
+    std::process::Command::new("cwebp")
+      .arg("-version")
+      .output()
+      .is_ok()
+  }
+
+  pub fn print_installation_guide() {
+    eprintln!("[❌] `cwebp` is not installed.");
+    eprintln!("    macOS:   brew install webp");
+    eprintln!("    Ubuntu:  sudo apt-get install webp");
+    eprintln!("    Other:   https://developers.google.com/speed/webp/download");
+  }
+
+  /// Converts a PNG at `input_path` to WebP at `output_path` via `cwebp`.
+  pub async fn convert_to_webp(input_path: &str, output_path: &str, quality: u8) -> Result<(), FigmaToolError> {
+    let status = Command::new("cwebp")
+      .args(["-quiet", "-q", &quality.to_string(), input_path, "-o", output_path])
+      .status()
+      .await
+      .map_err(|e| FigmaToolError::Conversion(format!("failed to spawn cwebp: {}", e)))?;
+
+    if !status.success() {
+      return Err(FigmaToolError::Conversion(format!("cwebp exited with {}", status)));
+    }
+
+    Ok(())
+  }
+
+  /// Converts a PNG at `input_path` to AVIF at `output_path` via `avifenc`.
+  pub async fn convert_to_avif(input_path: &str, output_path: &str, quality: u8) -> Result<(), FigmaToolError> {
+    let status = Command::new("avifenc")
+      .args(["-q", &quality.to_string(), input_path, output_path])
+      .status()
+      .await
+      .map_err(|e| FigmaToolError::Conversion(format!("failed to spawn avifenc: {}", e)))?;
+
+    if !status.success() {
+      return Err(FigmaToolError::Conversion(format!("avifenc exited with {}", status)));
+    }
+
+    Ok(())
+  }
+
+  /// Decodes `input_path` once and encodes it to WebP at `output_path`
+  /// in-process, on a blocking task so the async runtime isn't stalled.
+  pub async fn convert_to_webp_native(input_path: &str, output_path: &str, quality: u8) -> Result<(), FigmaToolError> {
+    let input_path = input_path.to_string();
+    let output_path = output_path.to_string();
+
+    tokio::task::spawn_blocking(move || {
+      let image = image::open(&input_path)
+        .map_err(|e| FigmaToolError::Conversion(format!("failed to decode {}: {}", input_path, e)))?
+        .to_rgba8();
+
+      let encoded = WebpEncoder::from_rgba(&image, image.width(), image.height()).encode(quality as f32);
+
+      std::fs::write(&output_path, &*encoded)
+        .map_err(|e| FigmaToolError::Conversion(format!("failed to write {}: {}", output_path, e)))
+    })
+    .await
+    .map_err(|e| FigmaToolError::Conversion(format!("native webp encoder task panicked: {}", e)))?
+  }
+
+  /// Decodes `input_path` once and encodes it to AVIF at `output_path`
+  /// in-process, on a blocking task so the async runtime isn't stalled.
+  pub async fn convert_to_avif_native(input_path: &str, output_path: &str, quality: u8) -> Result<(), FigmaToolError> {
+    let input_path = input_path.to_string();
+    let output_path = output_path.to_string();
+
+    tokio::task::spawn_blocking(move || {
+      let image = image::open(&input_path)
+        .map_err(|e| FigmaToolError::Conversion(format!("failed to decode {}: {}", input_path, e)))?
+        .to_rgba8();
+      let (width, height) = image.dimensions();
+      let pixels = image.into_raw();
+      let buffer = pixels.as_rgba();
+      let img = Img::new(buffer, width as usize, height as usize);
+
+      let encoded = AvifEncoder::new()
+        .with_quality(quality as f32)
+        .encode_rgba(img)
+        .map_err(|e| FigmaToolError::Conversion(format!("failed to encode avif: {}", e)))?;
+
+      std::fs::write(&output_path, encoded.avif_file)
+        .map_err(|e| FigmaToolError::Conversion(format!("failed to write {}: {}", output_path, e)))
+    })
+    .await
+    .map_err(|e| FigmaToolError::Conversion(format!("native avif encoder task panicked: {}", e)))?
+  }
+}
@@ -0,0 +1,140 @@
+use std::time::Duration;
+
+use futures::StreamExt;
+use indicatif::ProgressBar;
+use reqwest::header::CONTENT_TYPE;
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+
+use crate::errors::FigmaToolError;
+
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(8);
+
+/// Downloads exported assets from the URLs Figma hands back.
+pub struct ImageDownloader;
+
+impl ImageDownloader {
+  /// Downloads the resource at `url` and writes it to `{output_stem}.{ext}`,
+  /// where `ext` is derived from the response's `Content-Type` (falling back
+  /// to `fallback_format` when the header is missing or unrecognized), and
+  /// returns the final path that was written.
+  ///
+  /// Retries any failed attempt (request errors, non-success statuses, and
+  /// errors reading the body or writing the file) up to `max_retries` times
+  /// with exponential backoff.
+  ///
+  /// When `bar` is set, it's advanced by each chunk's byte count as the body
+  /// streams to disk, using the response's `Content-Length` for the total.
+  pub async fn download_image(
+    url: &str,
+    output_stem: &str,
+    fallback_format: &str,
+    max_retries: u32,
+    bar: Option<&ProgressBar>,
+  ) -> Result<String, FigmaToolError> {
+    let mut backoff = INITIAL_BACKOFF;
+    let mut attempt = 0;
+
+    loop {
+      match Self::try_download(url, output_stem, fallback_format, bar).await {
+        Ok(path) => return Ok(path),
+        Err(e) if attempt < max_retries => {
+          eprintln!(
+            "[⚠️]Retrying download ({}/{}) after error: {}",
+            attempt + 1,
+            max_retries,
+            e
+          );
+          tokio::time::sleep(backoff).await;
+          backoff = (backoff * 2).min(MAX_BACKOFF);
+          attempt += 1;
+        }
+        Err(e) => return Err(FigmaToolError::Download(format!("{} (after {} retries)", e, attempt))),
+      }
+    }
+  }
+
+  async fn try_download(
+    url: &str,
+    output_stem: &str,
+    fallback_format: &str,
+    bar: Option<&ProgressBar>,
+  ) -> Result<String, FigmaToolError> {
+    let response = reqwest::get(url)
+      .await
+      .map_err(|e| FigmaToolError::Download(format!("request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+      return Err(FigmaToolError::Download(format!(
+        "unexpected status {} for {}",
+        response.status(),
+        url
+      )));
+    }
+
+    if let Some(bar) = bar {
+      bar.set_position(0);
+      if let Some(len) = response.content_length() {
+        bar.set_length(len);
+      }
+    }
+
+    let ext = response
+      .headers()
+      .get(CONTENT_TYPE)
+      .and_then(|value| value.to_str().ok())
+      .and_then(extension_for_content_type)
+      .unwrap_or(fallback_format);
+    let output_path = format!("{}.{}", output_stem, ext);
+
+    let mut file = fs::File::create(&output_path).await?;
+    let mut stream = response.bytes_stream();
+
+    while let Some(chunk) = stream.next().await {
+      let chunk = chunk.map_err(|e| FigmaToolError::Download(format!("failed to read response body: {}", e)))?;
+      file.write_all(&chunk).await?;
+      if let Some(bar) = bar {
+        bar.inc(chunk.len() as u64);
+      }
+    }
+
+    Ok(output_path)
+  }
+}
+
+/// Maps a `Content-Type` header value to the file extension Figma's export
+/// formats normally show up as.
+fn extension_for_content_type(content_type: &str) -> Option<&'static str> {
+  match content_type.split(';').next().unwrap_or(content_type).trim() {
+    "image/png" => Some("png"),
+    "image/svg+xml" => Some("svg"),
+    "image/jpeg" => Some("jpg"),
+    "application/pdf" => Some("pdf"),
+    _ => None,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::extension_for_content_type;
+
+  #[test]
+  fn maps_known_content_types_to_extensions() {
+    assert_eq!(extension_for_content_type("image/png"), Some("png"));
+    assert_eq!(extension_for_content_type("image/svg+xml"), Some("svg"));
+    assert_eq!(extension_for_content_type("image/jpeg"), Some("jpg"));
+    assert_eq!(extension_for_content_type("application/pdf"), Some("pdf"));
+  }
+
+  #[test]
+  fn ignores_a_charset_suffix() {
+    assert_eq!(extension_for_content_type("image/svg+xml; charset=utf-8"), Some("svg"));
+  }
+
+  #[test]
+  fn falls_back_to_none_for_unknown_types() {
+    assert_eq!(extension_for_content_type("application/octet-stream"), None);
+    assert_eq!(extension_for_content_type(""), None);
+  }
+}
@@ -0,0 +1,20 @@
+use thiserror::Error;
+
+/// Crate-wide error type covering every fallible operation the CLI performs.
+#[derive(Debug, Error)]
+pub enum FigmaToolError {
+  #[error("configuration error: {0}")]
+  Config(String),
+
+  #[error("figma API error: {0}")]
+  FigmaApi(String),
+
+  #[error("download error: {0}")]
+  Download(String),
+
+  #[error("conversion error: {0}")]
+  Conversion(String),
+
+  #[error("filesystem error: {0}")]
+  Filesystem(#[from] std::io::Error),
+}
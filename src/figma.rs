@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::config::Config;
+use crate::errors::FigmaToolError;
+
+const FIGMA_API_BASE: &str = "https://api.figma.com/v1";
+
+#[derive(Debug, Deserialize)]
+struct FileNode {
+  id: String,
+  name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DocumentNode {
+  children: Vec<FileNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FileResponse {
+  document: DocumentNode,
+}
+
+#[derive(Debug, Deserialize)]
+struct ImagesResponse {
+  images: HashMap<String, Value>,
+}
+
+/// Talks to the Figma REST API to discover renderable nodes and resolve
+/// them to exportable image URLs.
+pub struct FigmaImageExtractor;
+
+impl FigmaImageExtractor {
+  /// Resolves exportable image URLs for a Figma file.
+  ///
+  /// When `node_id` is `None`, every top-level frame in the file's document
+  /// is exported. When `node_id` is `Some`, only that single node is
+  /// exported, which is how batch mode (see the `--file` flag of
+  /// [`crate::cli::Commands::Download`]) restricts a multi-file run to one frame per line. `export_format`
+  /// ("png", "svg", "jpg", or "pdf") and `scale` are passed straight through
+  /// to Figma's image render API.
+  ///
+  /// Returns `Ok(None)` when there is nothing to export.
+  pub async fn fetch_figma_images(
+    file_key: &str,
+    node_id: Option<&str>,
+    export_format: &str,
+    scale: f32,
+  ) -> Result<Option<Vec<(String, Value, String)>>, FigmaToolError> {
+    let config = Config::from_env()?;
+    let client = reqwest::Client::new();
+
+    match node_id {
+      Some(node_id) => {
+        let images_url = format!(
+          "{}/images/{}?ids={}&format={}&scale={}",
+          FIGMA_API_BASE, file_key, node_id, export_format, scale
+        );
+        let mut images: ImagesResponse = client
+          .get(&images_url)
+          .header("X-Figma-Token", &config.figma_token)
+          .send()
+          .await
+          .map_err(|e| FigmaToolError::FigmaApi(format!("failed to request figma images: {}", e)))?
+          .json()
+          .await
+          .map_err(|e| FigmaToolError::FigmaApi(format!("failed to parse figma images response: {}", e)))?;
+
+        Ok(
+          images
+            .images
+            .remove(node_id)
+            .filter(Value::is_string)
+            .map(|url| vec![(node_id.to_string(), url, node_id.to_string())]),
+        )
+      }
+      None => {
+        let file_url = format!("{}/files/{}", FIGMA_API_BASE, file_key);
+        let file: FileResponse = client
+          .get(&file_url)
+          .header("X-Figma-Token", &config.figma_token)
+          .send()
+          .await
+          .map_err(|e| FigmaToolError::FigmaApi(format!("failed to request figma file: {}", e)))?
+          .json()
+          .await
+          .map_err(|e| FigmaToolError::FigmaApi(format!("failed to parse figma file response: {}", e)))?;
+
+        if file.document.children.is_empty() {
+          return Ok(None);
+        }
+
+        let ids = file
+          .document
+          .children
+          .iter()
+          .map(|node| node.id.as_str())
+          .collect::<Vec<_>>()
+          .join(",");
+
+        let images_url = format!(
+          "{}/images/{}?ids={}&format={}&scale={}",
+          FIGMA_API_BASE, file_key, ids, export_format, scale
+        );
+        let mut images: ImagesResponse = client
+          .get(&images_url)
+          .header("X-Figma-Token", &config.figma_token)
+          .send()
+          .await
+          .map_err(|e| FigmaToolError::FigmaApi(format!("failed to request figma images: {}", e)))?
+          .json()
+          .await
+          .map_err(|e| FigmaToolError::FigmaApi(format!("failed to parse figma images response: {}", e)))?;
+
+        let results = file
+          .document
+          .children
+          .into_iter()
+          .filter_map(|node| {
+            images
+              .images
+              .remove(&node.id)
+              .filter(Value::is_string)
+              .map(|url| (node.id, url, node.name))
+          })
+          .collect::<Vec<_>>();
+
+        Ok(if results.is_empty() { None } else { Some(results) })
+      }
+    }
+  }
+}
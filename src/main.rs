@@ -1,133 +1,289 @@
+use std::path::Path;
 use std::sync::Arc;
 
 use clap::Parser;
 use futures::future;
+use futures::stream::{self, StreamExt};
 use tokio::fs;
+use tokio::io::AsyncBufReadExt;
 use tokio::sync::Semaphore;
 
 mod cli;
 mod config;
 mod converter;
 mod downloader;
+mod errors;
 mod figma;
+mod progress;
 
-use cli::{Cli, Commands};
+use cli::{Cli, Commands, Encoder};
+use config::Config;
 use converter::ImageConverter;
 use downloader::ImageDownloader;
+use errors::FigmaToolError;
 use figma::FigmaImageExtractor;
+use progress::Progress;
+
+/// One entry to export: a Figma file key and, optionally, a single node
+/// within it to restrict the export to.
+struct DownloadTarget {
+  file_key: String,
+  node_id: Option<String>,
+}
+
+impl DownloadTarget {
+  fn parse(line: &str) -> Self {
+    match line.split_once(':') {
+      Some((file_key, node_id)) => Self {
+        file_key: file_key.to_string(),
+        node_id: Some(node_id.to_string()),
+      },
+      None => Self {
+        file_key: line.to_string(),
+        node_id: None,
+      },
+    }
+  }
+}
+
+/// Reads newline-delimited `fileKey[:nodeId]` entries from `path`, skipping
+/// blank lines.
+async fn read_download_targets(path: &Path) -> Result<Vec<DownloadTarget>, FigmaToolError> {
+  let file = fs::File::open(path).await?;
+  let mut lines = tokio::io::BufReader::new(file).lines();
+
+  let mut targets = Vec::new();
+  while let Some(line) = lines.next_line().await? {
+    let line = line.trim();
+    if !line.is_empty() {
+      targets.push(DownloadTarget::parse(line));
+    }
+  }
+
+  Ok(targets)
+}
+
+/// Exports and downloads every image for one target into `target_dir`.
+#[allow(clippy::too_many_arguments)]
+async fn download_target(
+  target: &DownloadTarget,
+  target_dir: &Path,
+  concurrency: usize,
+  max_retries: u32,
+  export_format: &str,
+  scale: f32,
+  show_progress: bool,
+  quiet: bool,
+) -> Result<(), FigmaToolError> {
+  fs::create_dir_all(target_dir).await?;
+
+  let images =
+    FigmaImageExtractor::fetch_figma_images(&target.file_key, target.node_id.as_deref(), export_format, scale)
+      .await?;
+
+  match images {
+    Some(images) => {
+      let progress = Arc::new(Progress::new(images.len() as u64, &target.file_key, show_progress, quiet));
+
+      let downloads = images.into_iter().filter_map(|(_node_id, image_url, name)| {
+        image_url.as_str().map(|url| {
+          let sanitized_name = name.replace(['/', '\\', ':', '*', '?', '"', '<', '>', '|'], "_");
+          let output_stem = target_dir.join(&sanitized_name).to_string_lossy().to_string();
+          let url = url.to_string();
+          let bar = progress.add_download_bar(sanitized_name, None);
+          let progress = Arc::clone(&progress);
+
+          async move {
+            let result =
+              ImageDownloader::download_image(&url, &output_stem, export_format, max_retries, bar.as_ref()).await;
+            match &result {
+              Ok(path) => match &bar {
+                Some(bar) => bar.finish_with_message(format!("done: {}", path)),
+                None => println!("[✅]Downloaded: {}", path),
+              },
+              Err(e) => match &bar {
+                Some(bar) => bar.finish_with_message(format!("failed: {}", output_stem)),
+                None => eprintln!("[❌]Skipping {} after repeated failures: {}", output_stem, e),
+              },
+            }
+            progress.inc_overall();
+            result.is_ok()
+          }
+        })
+      });
+
+      let results = stream::iter(downloads).buffer_unordered(concurrency).collect::<Vec<bool>>().await;
+      progress.finish();
+
+      let failed = results.iter().filter(|ok| !**ok).count();
+      if failed > 0 {
+        eprintln!("[❌]{} of {} downloads failed and were skipped", failed, results.len());
+      }
+    }
+    None => println!("✅ No images found for {}.", target.file_key),
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::DownloadTarget;
+
+  #[test]
+  fn parses_a_bare_file_key_with_no_node_id() {
+    let target = DownloadTarget::parse("abc123");
+    assert_eq!(target.file_key, "abc123");
+    assert_eq!(target.node_id, None);
+  }
+
+  #[test]
+  fn parses_a_file_key_with_a_single_node_id() {
+    let target = DownloadTarget::parse("abc123:42");
+    assert_eq!(target.file_key, "abc123");
+    assert_eq!(target.node_id.as_deref(), Some("42"));
+  }
+
+  #[test]
+  fn only_splits_on_the_first_colon() {
+    let target = DownloadTarget::parse("abc123:1:23");
+    assert_eq!(target.file_key, "abc123");
+    assert_eq!(target.node_id.as_deref(), Some("1:23"));
+  }
+}
 
 #[tokio::main]
-async fn main() {
+async fn main() -> Result<(), FigmaToolError> {
   let cli = Cli::parse();
 
   match cli.command {
-    Commands::Download { download_dir } => {
-      if let Err(e) = fs::create_dir_all(&download_dir).await {
-        eprintln!("[❌]Failed to create download directory: {}", e);
-        return;
-      }
+    Commands::Download {
+      download_dir,
+      concurrency,
+      max_retries,
+      file,
+      export_format,
+      scale,
+      progress,
+      quiet,
+    } => {
+      fs::create_dir_all(&download_dir).await?;
 
-      match FigmaImageExtractor::fetch_figma_images().await {
-        Ok(Some(images)) => {
-          let downloads = images
-            .into_iter()
-            .filter_map(|(_node_id, image_url, name)| {
-              image_url.as_str().map(|url| {
-                let sanitized_name =
-                  name.replace(['/', '\\', ':', '*', '?', '"', '<', '>', '|'], "_");
-                let png_filename = download_dir.join(format!("{}.png", sanitized_name));
-                let png_path = png_filename.to_str().unwrap().to_string();
-                let url = url.to_string();
-
-                async move {
-                  match ImageDownloader::download_image(&url, &png_path).await {
-                    Ok(_) => {
-                      println!("[✅]Downloaded: {}", png_path);
-                      Ok(())
-                    }
-                    Err(e) => {
-                      eprintln!("❌ Failed to download: {}", e);
-                      Err(e)
-                    }
-                  }
-                }
-              })
-            })
-            .collect::<Vec<_>>();
-
-          if let Err(e) = future::try_join_all(downloads).await {
-            eprintln!("[❌]Some downloads failed: {}", e);
+      match file {
+        Some(batch_file) => {
+          let targets = read_download_targets(&batch_file).await?;
+          for target in &targets {
+            let target_dir = download_dir.join(&target.file_key);
+            let result = download_target(
+              target,
+              &target_dir,
+              concurrency,
+              max_retries,
+              &export_format,
+              scale,
+              progress,
+              quiet,
+            )
+            .await;
+
+            if let Err(e) = result {
+              eprintln!("[❌]Skipping {} after failure: {}", target.file_key, e);
+            }
           }
         }
-        Ok(None) => println!("✅ No images found."),
-        Err(e) => eprintln!("[❌]Failed to request figma API: {}", e),
+        None => {
+          let default_file_key = Config::from_env()?
+            .default_file_key
+            .ok_or_else(|| FigmaToolError::Config("no file key given: set FIGMA_FILE_KEY or pass --file".into()))?;
+          let target = DownloadTarget {
+            file_key: default_file_key,
+            node_id: None,
+          };
+          download_target(
+            &target,
+            &download_dir,
+            concurrency,
+            max_retries,
+            &export_format,
+            scale,
+            progress,
+            quiet,
+          )
+          .await?;
+        }
       }
     }
     Commands::Convert {
       input_dir,
       output_dir,
       format,
+      encoder,
+      quality,
+      progress,
+      quiet,
     } => {
       if format != "webp" && format != "avif" {
-        eprintln!("[❌]Unsupported format: {}", format);
-        return;
+        return Err(FigmaToolError::Conversion(format!("unsupported format: {}", format)));
       }
 
-      if format == "webp" && !ImageConverter::check_cwebp_installed() {
+      if encoder == Encoder::Cli && format == "webp" && !ImageConverter::check_cwebp_installed() {
         ImageConverter::print_installation_guide();
-        return;
+        return Ok(());
       }
 
-      if let Err(e) = fs::create_dir_all(&output_dir).await {
-        eprintln!("[❌]Failed to create output directory: {}", e);
-        return;
-      }
-
-      match fs::read_dir(&input_dir).await {
-        Ok(mut entries) => {
-          let mut conversion_tasks = Vec::new();
-          let semaphore = Arc::new(Semaphore::new(4));
+      fs::create_dir_all(&output_dir).await?;
 
-          while let Some(entry) = entries.next_entry().await.unwrap() {
-            let path = entry.path();
+      let mut entries = fs::read_dir(&input_dir).await?;
+      let mut inputs = Vec::new();
 
-            if path.extension().map_or(false, |ext| ext == "png") {
-              let file_stem = path.file_stem().unwrap().to_str().unwrap().to_string();
-              let output_path = output_dir.join(format!("{}.{}", &file_stem, format));
+      while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
 
-              let input_path = path.to_str().unwrap().to_string();
-              let output_path = output_path.to_str().unwrap().to_string();
-              let format = format.clone();
+        if path.extension().map_or(false, |ext| ext == "png") {
+          let Some(file_stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            eprintln!("[❌]Skipping {}: file name is not valid UTF-8", path.display());
+            continue;
+          };
+          let output_path = output_dir.join(format!("{}.{}", file_stem, format));
+          inputs.push((path.to_string_lossy().to_string(), output_path.to_string_lossy().to_string()));
+        }
+      }
 
-              let semaphore = Arc::clone(&semaphore);
+      let overall = Arc::new(Progress::new(inputs.len() as u64, "converted", progress, quiet));
+      let semaphore = Arc::new(Semaphore::new(4));
+      let mut conversion_tasks = Vec::new();
 
-              conversion_tasks.push(tokio::spawn(async move {
-                let _ = semaphore.acquire().await.unwrap();
+      for (input_path, output_path) in inputs {
+        let format = format.clone();
+        let semaphore = Arc::clone(&semaphore);
+        let overall = Arc::clone(&overall);
 
-                let result = match format.as_str() {
-                  "webp" => ImageConverter::convert_to_webp(&input_path, &output_path).await,
-                  "avif" => ImageConverter::convert_to_avif(&input_path, &output_path).await,
-                  _ => unreachable!(),
-                };
+        conversion_tasks.push(tokio::spawn(async move {
+          let _permit = semaphore.acquire().await.expect("semaphore is never closed");
+          let bar = overall.add_spinner(format!("converting {}", input_path));
 
-                match result {
-                  Ok(_) => println!("[✅]Converted: {} -> {}", input_path, output_path),
-                  Err(e) => eprintln!("[❌]Failed conversion: {}", e),
-                }
-              }));
-            }
-          }
+          let result = match (format.as_str(), encoder) {
+            ("webp", Encoder::Cli) => ImageConverter::convert_to_webp(&input_path, &output_path, quality).await,
+            ("avif", Encoder::Cli) => ImageConverter::convert_to_avif(&input_path, &output_path, quality).await,
+            ("webp", Encoder::Native) => ImageConverter::convert_to_webp_native(&input_path, &output_path, quality).await,
+            ("avif", Encoder::Native) => ImageConverter::convert_to_avif_native(&input_path, &output_path, quality).await,
+            _ => unreachable!(),
+          };
 
-          if let Err(e) = future::join_all(conversion_tasks)
-            .await
-            .into_iter()
-            .collect::<Result<Vec<_>, _>>()
-          {
-            eprintln!("[❌]Some conversions failed: {}", e);
+          match (&result, &bar) {
+            (Ok(_), Some(bar)) => bar.finish_with_message(format!("done: {} -> {}", input_path, output_path)),
+            (Ok(_), None) => println!("[✅]Converted: {} -> {}", input_path, output_path),
+            (Err(e), Some(bar)) => bar.finish_with_message(format!("failed: {}: {}", input_path, e)),
+            (Err(e), None) => eprintln!("[❌]Failed conversion of {}: {}", input_path, e),
           }
-        }
-        Err(e) => eprintln!("[❌]Failed to read input directory: {}", e),
+          overall.inc_overall();
+        }));
       }
+
+      future::join_all(conversion_tasks).await;
+      overall.finish();
     }
   }
+
+  Ok(())
 }
@@ -0,0 +1,77 @@
+use std::io::IsTerminal;
+use std::time::Duration;
+
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+
+/// Renders one progress bar per in-flight download/conversion plus an
+/// overall counter, via `indicatif::MultiProgress`.
+///
+/// Bars are only drawn when `--progress` was passed or stdout is an
+/// interactive terminal; `--quiet` always disables them. When disabled,
+/// callers fall back to their existing plain `println!`/`eprintln!`
+/// logging, so CI and piped output stay readable.
+pub struct Progress {
+  multi: Option<MultiProgress>,
+  overall: Option<ProgressBar>,
+}
+
+impl Progress {
+  pub fn new(total: u64, label: &str, force: bool, quiet: bool) -> Self {
+    if quiet || !(force || std::io::stdout().is_terminal()) {
+      return Self {
+        multi: None,
+        overall: None,
+      };
+    }
+
+    let multi = MultiProgress::new();
+    let overall = multi.add(ProgressBar::new(total));
+    overall.set_style(
+      ProgressStyle::with_template(&format!("{{msg}} {{pos}}/{{len}} {}", label))
+        .expect("static template is valid"),
+    );
+
+    Self {
+      multi: Some(multi),
+      overall: Some(overall),
+    }
+  }
+
+  /// Adds a byte-counted bar for a download, sized to `content_length` when
+  /// already known. The gauge template is used regardless, so a caller that
+  /// learns the length later (e.g. once the response headers arrive) can
+  /// call `bar.set_length(..)` and the proportional bar fills in correctly.
+  pub fn add_download_bar(&self, message: String, content_length: Option<u64>) -> Option<ProgressBar> {
+    let multi = self.multi.as_ref()?;
+    let bar = multi.add(ProgressBar::new(content_length.unwrap_or(0)));
+    bar.set_style(
+      ProgressStyle::with_template("{spinner} {msg} [{bar:20}] {bytes}/{total_bytes}")
+        .expect("static template is valid")
+        .progress_chars("=>-"),
+    );
+    bar.set_message(message);
+    Some(bar)
+  }
+
+  /// Adds a spinner for a task with no measurable progress (conversions).
+  pub fn add_spinner(&self, message: String) -> Option<ProgressBar> {
+    let multi = self.multi.as_ref()?;
+    let bar = multi.add(ProgressBar::new_spinner());
+    bar.set_style(ProgressStyle::with_template("{spinner} {msg}").expect("static template is valid"));
+    bar.set_message(message);
+    bar.enable_steady_tick(Duration::from_millis(100));
+    Some(bar)
+  }
+
+  pub fn inc_overall(&self) {
+    if let Some(overall) = &self.overall {
+      overall.inc(1);
+    }
+  }
+
+  pub fn finish(&self) {
+    if let Some(overall) = &self.overall {
+      overall.finish_and_clear();
+    }
+  }
+}